@@ -1,5 +1,16 @@
-use std::ops::Deref;
+use std::ops::{Bound, Deref, RangeBounds};
 use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::mem;
+
+mod map;
+pub use map::SortedMap;
+
+mod sorted_by;
+pub use sorted_by::SortedUniqueVecBy;
+
+mod reverse;
+pub use reverse::ReverseSortedUniqueVec;
 
 /// A `Vec` in sorted order without duplicates.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,6 +31,58 @@ pub enum LeftOrRight {
     Right,
 }
 
+/// One step of the shared two-pointer walk over two sorted-unique slices, used by every
+/// `merge`-family method on `SortedUniqueVec`, `SortedUniqueVecBy` and
+/// `ReverseSortedUniqueVec` so the iteration/peek logic lives in exactly one place.
+pub(crate) enum Take<'a, T: 'a> {
+    Left(&'a T),
+    Right(&'a T),
+    Both(&'a T, &'a T),
+}
+
+/// Walks `left` and `right` in lock-step order according to `cmp`, calling `f` once per
+/// element with `Take::Left`/`Take::Right` for an element present in only one side (per
+/// `cmp`'s ordering), and once per pair with `Take::Both` when `cmp` reports them equal.
+///
+/// `cmp` need not be `T`'s natural `Ord`: callers project onto a key or reverse the
+/// comparison to reuse this walk for `SortedUniqueVecBy` and `ReverseSortedUniqueVec`.
+pub(crate) fn merge_walk<'a, T, C, F>(left: &'a [T], right: &'a [T], mut cmp: C, mut f: F)
+    where C: FnMut(&T, &T) -> Ordering,
+          F: FnMut(Take<'a, T>)
+{
+    let mut left_iter = left.iter().peekable();
+    let mut right_iter = right.iter().peekable();
+
+    loop {
+        match (left_iter.peek(), right_iter.peek()) {
+            (Some(l), Some(r)) => {
+                match cmp(l, r) {
+                    Ordering::Less => f(Take::Left(left_iter.next().unwrap())),
+                    Ordering::Greater => f(Take::Right(right_iter.next().unwrap())),
+                    Ordering::Equal => {
+                        f(Take::Both(left_iter.next().unwrap(), right_iter.next().unwrap()));
+                    }
+                }
+            }
+            (Some(_), None) => {
+                for item in left_iter {
+                    f(Take::Left(item));
+                }
+                break;
+            }
+            (None, Some(_)) => {
+                for item in right_iter {
+                    f(Take::Right(item));
+                }
+                break;
+            }
+            (None, None) => {
+                break;
+            }
+        }
+    }
+}
+
 impl<T: Ord + Clone> SortedUniqueVec<T> {
     pub fn new() -> Self {
         SortedUniqueVec { vec: Vec::new() }
@@ -83,6 +146,60 @@ impl<T: Ord + Clone> SortedUniqueVec<T> {
         }
     }
 
+    fn range_bounds<R: RangeBounds<T>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(t) => {
+                match self.vec.binary_search(t) {
+                    Ok(idx) => idx,
+                    Err(idx) => idx,
+                }
+            }
+            Bound::Excluded(t) => {
+                match self.vec.binary_search(t) {
+                    Ok(idx) => idx + 1,
+                    Err(idx) => idx,
+                }
+            }
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(t) => {
+                match self.vec.binary_search(t) {
+                    Ok(idx) => idx + 1,
+                    Err(idx) => idx,
+                }
+            }
+            Bound::Excluded(t) => {
+                match self.vec.binary_search(t) {
+                    Ok(idx) => idx,
+                    Err(idx) => idx,
+                }
+            }
+            Bound::Unbounded => self.vec.len(),
+        };
+
+        (start, end)
+    }
+
+    /// Returns the contiguous sub-slice of elements within `range`, found in O(log n) by
+    /// binary-searching for the lower and upper bounds.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> &[T] {
+        let (start, end) = self.range_bounds(range);
+        &self.vec[start..end]
+    }
+
+    /// The number of elements within `range`. Equivalent to `self.range(range).len()`.
+    pub fn range_count<R: RangeBounds<T>>(&self, range: R) -> usize {
+        self.range(range).len()
+    }
+
+    /// Removes and yields the elements within `range`.
+    pub fn drain_range<R: RangeBounds<T>>(&mut self, range: R) -> ::std::vec::Drain<'_, T> {
+        let (start, end) = self.range_bounds(range);
+        self.vec.drain(start..end)
+    }
+
     /// Insert `element` into the sorted list.
     ///
     /// Panics if an element with the same key (according to the Eq trait) already exists.
@@ -97,6 +214,65 @@ impl<T: Ord + Clone> SortedUniqueVec<T> {
         }
     }
 
+    /// Builds a `SortedUniqueVec` from an unsorted, possibly duplicate-containing `Vec`.
+    ///
+    /// Sorts with `sort_unstable` and collapses duplicate runs, giving O(n log n) total
+    /// instead of the O(n^2) cost of n separate `insert` calls.
+    pub fn from_unsorted(mut v: Vec<T>) -> Self {
+        v.sort_unstable();
+        v.dedup();
+        debug_assert!(is_sorted_unique(&v));
+        SortedUniqueVec { vec: v }
+    }
+
+    /// Folds an already sorted-unique slice into `self` using the same linear two-pointer
+    /// merge as `merge`, rather than calling `insert` element by element.
+    pub fn extend_presorted(&mut self, other: &[T]) {
+        debug_assert!(is_sorted_unique(other));
+        let other = SortedUniqueVec { vec: other.to_vec() };
+        let merged = self.merge(&other, &|_, _| LeftOrRight::Left);
+        self.vec = merged.vec;
+    }
+
+    /// Removes `element` from the sorted list, returning it if it was present.
+    pub fn remove(&mut self, element: &T) -> Option<T> {
+        match self.vec.binary_search(element) {
+            Ok(idx) => Some(self.vec.remove(idx)),
+            Err(_idx) => None,
+        }
+    }
+
+    /// Removes and returns the element at `idx`.
+    pub fn remove_index(&mut self, idx: usize) -> T {
+        self.vec.remove(idx)
+    }
+
+    /// Inserts `element` only if no equal element exists yet.
+    ///
+    /// Returns the index of the element and whether it was newly inserted.
+    pub fn find_or_insert(&mut self, element: T) -> (usize, bool) {
+        match self.vec.binary_search(&element) {
+            Ok(idx) => (idx, false),
+            Err(idx) => {
+                self.vec.insert(idx, element);
+                (idx, true)
+            }
+        }
+    }
+
+    /// Adds `element` to the list, replacing the existing element that is equal to it, if any.
+    ///
+    /// Returns the replaced element.
+    pub fn replace(&mut self, element: T) -> Option<T> {
+        match self.vec.binary_search(&element) {
+            Ok(idx) => Some(mem::replace(&mut self.vec[idx], element)),
+            Err(idx) => {
+                self.vec.insert(idx, element);
+                None
+            }
+        }
+    }
+
     /// Merges `self` and `other` into a new SortedVec.
     /// `choose_equal` decides which one of two equal values to take.
     pub fn merge<F>(&self, other: &Self, choose_equal: &F) -> Self
@@ -104,75 +280,67 @@ impl<T: Ord + Clone> SortedUniqueVec<T> {
     {
         let mut vec = Vec::with_capacity(self.len() + other.len());
 
-        let mut left_iter = self.vec.iter().peekable();
-        let mut right_iter = other.vec.iter().peekable();
-
-        enum Take {
-            OneLeft,
-            OneRight,
-            Both,
-            AllLeft,
-            AllRight,
-        };
-
-        loop {
-            let take;
-
-            match (left_iter.peek(), right_iter.peek()) {
-                (Some(l), Some(r)) => {
-                    if l < r {
-                        take = Take::OneLeft;
-                    } else if r < l {
-                        take = Take::OneRight;
-                    } else {
-                        take = Take::Both;
-                    }
-                }
-                (Some(_), None) => {
-                    take = Take::AllLeft;
-                }
-                (None, Some(_)) => {
-                    take = Take::AllRight;
-                }
-                (None, None) => {
-                    break;
-                }
-            }
+        merge_walk(&self.vec, &other.vec, |l, r| l.cmp(r), |take| {
             match take {
-                Take::OneLeft => {
-                    vec.push((*left_iter.next().unwrap()).clone());
-                }
-                Take::OneRight => {
-                    vec.push((*right_iter.next().unwrap()).clone());
-                }
-                Take::Both => {
-                    // two equal values
-                    let left_value = left_iter.next().unwrap();
-                    let right_value = right_iter.next().unwrap();
-                    match choose_equal(left_value, right_value) {
-                        LeftOrRight::Left => {
-                            vec.push((*left_value).clone());
-                        }
-                        LeftOrRight::Right => {
-                            vec.push((*right_value).clone());
-                        }
-                    }
-                }
-                Take::AllLeft => {
-                    for item in left_iter {
-                        vec.push((*item).clone());
+                Take::Left(l) => vec.push(l.clone()),
+                Take::Right(r) => vec.push(r.clone()),
+                Take::Both(l, r) => {
+                    match choose_equal(l, r) {
+                        LeftOrRight::Left => vec.push(l.clone()),
+                        LeftOrRight::Right => vec.push(r.clone()),
                     }
-                    break;
-                }
-                Take::AllRight => {
-                    for item in right_iter {
-                        vec.push((*item).clone());
-                    }
-                    break;
                 }
+            }
+        });
+
+        debug_assert!(is_sorted_unique(&vec));
+        SortedUniqueVec { vec: vec }
+    }
+
+    /// The set of elements that are in `self` or `other` (or both).
+    pub fn union(&self, other: &Self) -> Self {
+        self.merge(other, &|_, _| LeftOrRight::Left)
+    }
 
+    /// The set of elements that are in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut vec = Vec::with_capacity(self.len().min(other.len()));
+
+        merge_walk(&self.vec, &other.vec, |l, r| l.cmp(r), |take| {
+            if let Take::Both(l, _r) = take {
+                vec.push(l.clone());
             }
-        }
+        });
+
+        debug_assert!(is_sorted_unique(&vec));
+        SortedUniqueVec { vec: vec }
+    }
+
+    /// The set of elements that are in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut vec = Vec::with_capacity(self.len());
+
+        merge_walk(&self.vec, &other.vec, |l, r| l.cmp(r), |take| {
+            if let Take::Left(l) = take {
+                vec.push(l.clone());
+            }
+        });
+
+        debug_assert!(is_sorted_unique(&vec));
+        SortedUniqueVec { vec: vec }
+    }
+
+    /// The set of elements that are in `self` or `other` but not both.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut vec = Vec::with_capacity(self.len() + other.len());
+
+        merge_walk(&self.vec, &other.vec, |l, r| l.cmp(r), |take| {
+            match take {
+                Take::Left(l) => vec.push(l.clone()),
+                Take::Right(r) => vec.push(r.clone()),
+                Take::Both(_l, _r) => {}
+            }
+        });
 
         debug_assert!(is_sorted_unique(&vec));
         SortedUniqueVec { vec: vec }
@@ -193,6 +361,19 @@ impl<T: Ord + Clone> Deref for SortedUniqueVec<T> {
     }
 }
 
+impl<T: Ord + Clone> FromIterator<T> for SortedUniqueVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SortedUniqueVec::from_unsorted(iter.into_iter().collect())
+    }
+}
+
+impl<T: Ord + Clone> Extend<T> for SortedUniqueVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let other = SortedUniqueVec::from_unsorted(iter.into_iter().collect());
+        self.extend_presorted(&other);
+    }
+}
+
 #[test]
 fn test_insert() {
     let mut s = SortedUniqueVec::new();
@@ -227,6 +408,24 @@ fn test_merge() {
     assert_eq!(&[0, 1, 5, 7, 8, 9, 55][..], r.as_ref());
 }
 
+#[test]
+fn test_set_ops() {
+    let mut s1 = SortedUniqueVec::new();
+    for i in &[0, 1, 5, 8] {
+        s1.push(*i);
+    }
+
+    let mut s2 = SortedUniqueVec::new();
+    for i in &[1, 5, 7, 9, 55] {
+        s2.push(*i);
+    }
+
+    assert_eq!(&[0, 1, 5, 7, 8, 9, 55][..], s1.union(&s2).as_ref());
+    assert_eq!(&[1, 5][..], s1.intersection(&s2).as_ref());
+    assert_eq!(&[0, 8][..], s1.difference(&s2).as_ref());
+    assert_eq!(&[0, 7, 8, 9, 55][..], s1.symmetric_difference(&s2).as_ref());
+}
+
 #[test]
 fn test_push_ok() {
     let mut s = SortedUniqueVec::new();
@@ -247,6 +446,85 @@ fn test_push_fail() {
     s.push(1);
 }
 
+#[test]
+fn test_from_unsorted() {
+    let s = SortedUniqueVec::from_unsorted(vec![5, 1, 8, 1, 0, 5]);
+    assert!(is_sorted_unique(&s));
+    assert_eq!(&[0, 1, 5, 8][..], s.as_ref());
+}
+
+#[test]
+fn test_extend_presorted() {
+    let mut s = SortedUniqueVec::from_unsorted(vec![1, 5, 8]);
+    s.extend_presorted(&[0, 5, 9]);
+    assert_eq!(&[0, 1, 5, 8, 9][..], s.as_ref());
+}
+
+#[test]
+fn test_from_iterator_and_extend() {
+    let mut s: SortedUniqueVec<i32> = vec![5, 1, 8, 1].into_iter().collect();
+    assert_eq!(&[1, 5, 8][..], s.as_ref());
+    s.extend(vec![0, 8, 9]);
+    assert_eq!(&[0, 1, 5, 8, 9][..], s.as_ref());
+}
+
+#[test]
+fn test_remove() {
+    let mut s = SortedUniqueVec::new();
+    for i in &[0, 1, 5, 8] {
+        s.push(*i);
+    }
+    assert_eq!(Some(1), s.remove(&1));
+    assert_eq!(None, s.remove(&1));
+    assert_eq!(&[0, 5, 8][..], s.as_ref());
+    assert_eq!(5, s.remove_index(1));
+    assert_eq!(&[0, 8][..], s.as_ref());
+}
+
+#[test]
+fn test_find_or_insert() {
+    let mut s = SortedUniqueVec::new();
+    assert_eq!((0, true), s.find_or_insert(5));
+    assert_eq!((1, true), s.find_or_insert(8));
+    assert_eq!((0, false), s.find_or_insert(5));
+    assert_eq!(&[5, 8][..], s.as_ref());
+}
+
+#[test]
+fn test_replace() {
+    let mut s = SortedUniqueVec::new();
+    s.push(1);
+    s.push(2);
+    assert_eq!(None, s.replace(3));
+    assert_eq!(Some(1), s.replace(1));
+    assert_eq!(&[1, 2, 3][..], s.as_ref());
+}
+
+#[test]
+fn test_range() {
+    let mut s = SortedUniqueVec::new();
+    for i in &[0, 1, 5, 7, 8, 9, 55] {
+        s.push(*i);
+    }
+    assert_eq!(&[5, 7, 8][..], s.range(5..9));
+    assert_eq!(&[5, 7, 8, 9][..], s.range(5..=9));
+    assert_eq!(&[0, 1][..], s.range(..5));
+    assert_eq!(&[9, 55][..], s.range(9..));
+    assert_eq!(&s[..], s.range(..));
+    assert_eq!(3, s.range_count(5..9));
+}
+
+#[test]
+fn test_drain_range() {
+    let mut s = SortedUniqueVec::new();
+    for i in &[0, 1, 5, 7, 8, 9, 55] {
+        s.push(*i);
+    }
+    let drained: Vec<_> = s.drain_range(5..9).collect();
+    assert_eq!(vec![5, 7, 8], drained);
+    assert_eq!(&[0, 1, 9, 55][..], s.as_ref());
+}
+
 #[test]
 #[should_panic]
 fn test_push_fail2() {
@@ -0,0 +1,195 @@
+use std::mem;
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
+
+/// A map backed by a `Vec<(K, V)>` kept sorted by key, without duplicate keys.
+///
+/// Lookups reuse the same `binary_search_by` approach as `SortedUniqueVec`'s
+/// `index_by`/`find_by` helpers, just comparing on the key half of the pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortedMap<K: Ord + Clone, V: Clone> {
+    vec: Vec<(K, V)>,
+}
+
+pub fn is_sorted_unique_by_key<K: Ord, V>(slice: &[(K, V)]) -> bool {
+    if slice.len() < 2 {
+        true
+    } else {
+        slice.windows(2).all(|win| win[0].0 < win[1].0)
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> SortedMap<K, V> {
+    pub fn new() -> Self {
+        SortedMap { vec: Vec::new() }
+    }
+
+    pub fn with_capacity(capa: usize) -> Self {
+        SortedMap { vec: Vec::with_capacity(capa) }
+    }
+
+    /// Builds a `SortedMap` from a `Vec` of pairs that is already sorted by key and
+    /// contains no duplicate keys.
+    ///
+    /// Panics in debug builds if that precondition does not hold.
+    pub fn from_presorted(v: Vec<(K, V)>) -> Self {
+        debug_assert!(is_sorted_unique_by_key(&v));
+        SortedMap { vec: v }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    fn index_of(&self, key: &K) -> Result<usize, usize> {
+        self.vec.binary_search_by(|(k, _)| k.cmp(key))
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index_of(key).is_ok()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.index_of(key) {
+            Ok(idx) => Some(&self.vec[idx].1),
+            Err(_idx) => None,
+        }
+    }
+
+    /// Only the value is handed out mutably; the key cannot be changed, so the sort order holds.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.index_of(key) {
+            Ok(idx) => Some(&mut self.vec[idx].1),
+            Err(_idx) => None,
+        }
+    }
+
+    fn range_bounds<R: RangeBounds<K>>(&self, range: R) -> (usize, usize) {
+        let key_search = |k: &K| self.vec.binary_search_by(|(probe, _)| probe.cmp(k));
+
+        let start = match range.start_bound() {
+            Bound::Included(k) => key_search(k).unwrap_or_else(|idx| idx),
+            Bound::Excluded(k) => key_search(k).map(|idx| idx + 1).unwrap_or_else(|idx| idx),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(k) => key_search(k).map(|idx| idx + 1).unwrap_or_else(|idx| idx),
+            Bound::Excluded(k) => key_search(k).unwrap_or_else(|idx| idx),
+            Bound::Unbounded => self.vec.len(),
+        };
+
+        (start, end)
+    }
+
+    /// Returns the contiguous sub-slice of entries whose key falls within `range`.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> &[(K, V)] {
+        let (start, end) = self.range_bounds(range);
+        &self.vec[start..end]
+    }
+
+    /// The number of entries whose key falls within `range`.
+    pub fn range_count<R: RangeBounds<K>>(&self, range: R) -> usize {
+        self.range(range).len()
+    }
+
+    /// Removes and yields the entries whose key falls within `range`.
+    pub fn drain_range<R: RangeBounds<K>>(&mut self, range: R) -> ::std::vec::Drain<'_, (K, V)> {
+        let (start, end) = self.range_bounds(range);
+        self.vec.drain(start..end)
+    }
+
+    /// Insert `value` for `key`, returning the previous value if `key` already existed.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.index_of(&key) {
+            Ok(idx) => Some(mem::replace(&mut self.vec[idx].1, value)),
+            Err(idx) => {
+                self.vec.insert(idx, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the entry for `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match self.index_of(key) {
+            Ok(idx) => Some(self.vec.remove(idx).1),
+            Err(_idx) => None,
+        }
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Index<&K> for SortedMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> IndexMut<&K> for SortedMap<K, V> {
+    fn index_mut(&mut self, key: &K) -> &mut V {
+        self.get_mut(key).expect("no entry found for key")
+    }
+}
+
+#[test]
+fn test_map_insert_get() {
+    let mut m = SortedMap::new();
+    assert_eq!(None, m.insert(5, "five"));
+    assert_eq!(None, m.insert(1, "one"));
+    assert_eq!(None, m.insert(8, "eight"));
+    assert_eq!(Some("five"), m.insert(5, "FIVE"));
+    assert_eq!(3, m.len());
+    assert_eq!(Some(&"FIVE"), m.get(&5));
+    assert_eq!(Some(&"one"), m.get(&1));
+    assert_eq!(None, m.get(&42));
+}
+
+#[test]
+fn test_map_index() {
+    let mut m = SortedMap::new();
+    m.insert(1, 10);
+    m.insert(2, 20);
+    assert_eq!(10, m[&1]);
+    m[&2] = 21;
+    assert_eq!(21, m[&2]);
+}
+
+#[test]
+fn test_map_remove() {
+    let mut m = SortedMap::new();
+    m.insert(1, "a");
+    m.insert(2, "b");
+    assert_eq!(Some("a"), m.remove(&1));
+    assert_eq!(None, m.remove(&1));
+    assert_eq!(1, m.len());
+    assert!(!m.contains_key(&1));
+    assert!(m.contains_key(&2));
+}
+
+#[test]
+fn test_map_range() {
+    let m = SortedMap::from_presorted(vec![(1, "a"), (3, "b"), (5, "c"), (7, "d")]);
+    assert_eq!(&[(3, "b"), (5, "c")][..], m.range(2..6));
+    assert_eq!(&[(1, "a")][..], m.range(..3));
+    assert_eq!(2, m.range_count(2..6));
+}
+
+#[test]
+fn test_map_drain_range() {
+    let mut m = SortedMap::from_presorted(vec![(1, "a"), (3, "b"), (5, "c"), (7, "d")]);
+    let drained: Vec<_> = m.drain_range(2..6).collect();
+    assert_eq!(vec![(3, "b"), (5, "c")], drained);
+    assert_eq!(2, m.len());
+}
+
+#[test]
+fn test_map_from_presorted() {
+    let m = SortedMap::from_presorted(vec![(1, "a"), (2, "b"), (3, "c")]);
+    assert_eq!(3, m.len());
+    assert_eq!(Some(&"b"), m.get(&2));
+}
@@ -0,0 +1,185 @@
+use std::ops::{Bound, Deref, RangeBounds};
+
+use super::{LeftOrRight, Take, merge_walk};
+
+/// A `Vec` in descending sorted order without duplicates.
+///
+/// Supports the same use-cases as `SortedUniqueVec`, but for descending order, without
+/// requiring callers to wrap every element in `std::cmp::Reverse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseSortedUniqueVec<T: Ord + Clone> {
+    vec: Vec<T>,
+}
+
+pub fn is_reverse_sorted_unique<T: Ord>(slice: &[T]) -> bool {
+    if slice.len() < 2 {
+        true
+    } else {
+        slice.windows(2).all(|win| win[0] > win[1])
+    }
+}
+
+impl<T: Ord + Clone> ReverseSortedUniqueVec<T> {
+    pub fn new() -> Self {
+        ReverseSortedUniqueVec { vec: Vec::new() }
+    }
+
+    pub fn with_capacity(capa: usize) -> Self {
+        ReverseSortedUniqueVec { vec: Vec::with_capacity(capa) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    fn search(&self, element: &T) -> Result<usize, usize> {
+        self.vec.binary_search_by(|probe| element.cmp(probe))
+    }
+
+    /// Panics if descending sort order is destroyed by this push operation.
+    pub fn push(&mut self, item: T) {
+        if let Some(last_item) = self.last() {
+            assert!(last_item > &item);
+        }
+        self.vec.push(item);
+    }
+
+    pub fn contains(&self, element: &T) -> bool {
+        self.search(element).is_ok()
+    }
+
+    /// Insert `element` into the descending sorted list.
+    ///
+    /// Panics if an element with the same key (according to the Eq trait) already exists.
+    pub fn insert(&mut self, element: T) {
+        match self.search(&element) {
+            Ok(_idx) => {
+                panic!("Element already exists");
+            }
+            Err(idx) => {
+                self.vec.insert(idx, element);
+            }
+        }
+    }
+
+    fn range_bounds<R: RangeBounds<T>>(&self, range: R) -> (usize, usize) {
+        // The vector descends, so the upper bound of `range` is searched for first
+        // (it determines the start index) and the lower bound last.
+        let start = match range.end_bound() {
+            Bound::Included(t) => self.search(t).unwrap_or_else(|idx| idx),
+            Bound::Excluded(t) => self.search(t).map(|idx| idx + 1).unwrap_or_else(|idx| idx),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.start_bound() {
+            Bound::Included(t) => self.search(t).map(|idx| idx + 1).unwrap_or_else(|idx| idx),
+            Bound::Excluded(t) => self.search(t).unwrap_or_else(|idx| idx),
+            Bound::Unbounded => self.vec.len(),
+        };
+
+        (start, end)
+    }
+
+    /// Returns the contiguous sub-slice of elements falling within `range`.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> &[T] {
+        let (start, end) = self.range_bounds(range);
+        &self.vec[start..end]
+    }
+
+    /// Merges `self` and `other` into a new `ReverseSortedUniqueVec`.
+    /// `choose_equal` decides which one of two equal values to take.
+    pub fn merge<F>(&self, other: &Self, choose_equal: &F) -> Self
+        where F: Fn(&T, &T) -> LeftOrRight
+    {
+        let mut vec = Vec::with_capacity(self.len() + other.len());
+
+        // Descending order means the element that should sort first is the *larger* one, so
+        // the comparator is `T`'s natural `Ord` with its arguments swapped.
+        merge_walk(&self.vec, &other.vec, |l, r| r.cmp(l), |take| {
+            match take {
+                Take::Left(l) => vec.push(l.clone()),
+                Take::Right(r) => vec.push(r.clone()),
+                Take::Both(l, r) => {
+                    match choose_equal(l, r) {
+                        LeftOrRight::Left => vec.push(l.clone()),
+                        LeftOrRight::Right => vec.push(r.clone()),
+                    }
+                }
+            }
+        });
+
+        debug_assert!(is_reverse_sorted_unique(&vec));
+        ReverseSortedUniqueVec { vec: vec }
+    }
+}
+
+impl<T: Ord + Clone> AsRef<[T]> for ReverseSortedUniqueVec<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T: Ord + Clone> Deref for ReverseSortedUniqueVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+#[test]
+fn test_reverse_insert() {
+    let mut s = ReverseSortedUniqueVec::new();
+    s.insert(5);
+    s.insert(1);
+    s.insert(8);
+    s.insert(0);
+    assert!(is_reverse_sorted_unique(&s));
+    assert_eq!(&[8, 5, 1, 0][..], s.as_ref());
+}
+
+#[test]
+fn test_reverse_push_ok() {
+    let mut s = ReverseSortedUniqueVec::new();
+    s.push(6);
+    s.push(5);
+    s.push(0);
+    assert!(is_reverse_sorted_unique(&s));
+    assert_eq!(&[6, 5, 0][..], s.as_ref());
+}
+
+#[test]
+#[should_panic]
+fn test_reverse_push_fail() {
+    let mut s = ReverseSortedUniqueVec::new();
+    s.push(5);
+    s.push(5);
+}
+
+#[test]
+fn test_reverse_range() {
+    let mut s = ReverseSortedUniqueVec::new();
+    for i in &[55, 9, 8, 7, 5, 1, 0] {
+        s.push(*i);
+    }
+    assert_eq!(&[8, 7, 5][..], s.range(5..9));
+}
+
+#[test]
+fn test_reverse_merge() {
+    let mut s1 = ReverseSortedUniqueVec::new();
+    s1.insert(8);
+    s1.insert(5);
+    s1.insert(1);
+    s1.insert(0);
+
+    let mut s2 = ReverseSortedUniqueVec::new();
+    s2.insert(55);
+    s2.insert(9);
+    s2.insert(5);
+    s2.insert(1);
+
+    let r = s1.merge(&s2, &|_, _| LeftOrRight::Left);
+    assert!(is_reverse_sorted_unique(&r));
+    assert_eq!(&[55, 9, 8, 5, 1, 0][..], r.as_ref());
+}
@@ -0,0 +1,192 @@
+use std::ops::{Bound, Deref, RangeBounds};
+
+use super::{LeftOrRight, Take, merge_walk};
+
+/// A `Vec` kept sorted and unique by a projected key `K`, rather than by `T`'s own `Ord`
+/// implementation.
+///
+/// All lookups binary-search on `key(item)`, so callers get O(log n) `contains_key`/`get`/
+/// `range` (and `insert` finds its position by binary search, then shifts the tail) while
+/// storing records sorted by just one of their fields.
+pub struct SortedUniqueVecBy<T, K: Ord, F: Fn(&T) -> K> {
+    vec: Vec<T>,
+    key: F,
+}
+
+fn is_sorted_unique_by<T, K: Ord, F: Fn(&T) -> K>(slice: &[T], key: &F) -> bool {
+    if slice.len() < 2 {
+        true
+    } else {
+        slice.windows(2).all(|win| key(&win[0]) < key(&win[1]))
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> SortedUniqueVecBy<T, K, F> {
+    pub fn new(key: F) -> Self {
+        SortedUniqueVecBy { vec: Vec::new(), key: key }
+    }
+
+    pub fn with_capacity(capa: usize, key: F) -> Self {
+        SortedUniqueVecBy { vec: Vec::with_capacity(capa), key: key }
+    }
+
+    fn key_of(&self, item: &T) -> K {
+        (self.key)(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Panics if sorted order is destroyed by this push operation.
+    pub fn push(&mut self, item: T) {
+        if let Some(last_item) = self.vec.last() {
+            assert!(self.key_of(last_item) < self.key_of(&item));
+        }
+        self.vec.push(item);
+    }
+
+    fn index_by_key(&self, k: &K) -> Result<usize, usize> {
+        self.vec.binary_search_by(|probe| self.key_of(probe).cmp(k))
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.index_by_key(k).is_ok()
+    }
+
+    pub fn get(&self, k: &K) -> Option<&T> {
+        match self.index_by_key(k) {
+            Ok(idx) => self.vec.get(idx),
+            Err(_idx) => None,
+        }
+    }
+
+    /// Insert `element` into the sorted list.
+    ///
+    /// Panics if an element with the same key already exists.
+    pub fn insert(&mut self, element: T) {
+        let k = self.key_of(&element);
+        match self.index_by_key(&k) {
+            Ok(_idx) => {
+                panic!("Element already exists");
+            }
+            Err(idx) => {
+                self.vec.insert(idx, element);
+            }
+        }
+    }
+
+    fn range_bounds<R: RangeBounds<K>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(k) => self.index_by_key(k).unwrap_or_else(|idx| idx),
+            Bound::Excluded(k) => self.index_by_key(k).map(|idx| idx + 1).unwrap_or_else(|idx| idx),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(k) => self.index_by_key(k).map(|idx| idx + 1).unwrap_or_else(|idx| idx),
+            Bound::Excluded(k) => self.index_by_key(k).unwrap_or_else(|idx| idx),
+            Bound::Unbounded => self.vec.len(),
+        };
+
+        (start, end)
+    }
+
+    /// Returns the contiguous sub-slice of elements whose key falls within `range`.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> &[T] {
+        let (start, end) = self.range_bounds(range);
+        &self.vec[start..end]
+    }
+
+    /// Merges `self` and `other` into a new `SortedUniqueVecBy`, keyed the same way as `self`.
+    /// `choose_equal` decides which one of two equal-key values to take.
+    ///
+    /// Note: `self` and `other` must share the same key-extractor type `F`; a shared `fn` item
+    /// or a cloned closure value satisfies this, two independently-written closure literals do
+    /// not (each closure literal has its own anonymous type).
+    pub fn merge<FC>(&self, other: &Self, choose_equal: &FC) -> Self
+        where T: Clone,
+              F: Clone,
+              FC: Fn(&T, &T) -> LeftOrRight
+    {
+        let mut vec = Vec::with_capacity(self.len() + other.len());
+
+        merge_walk(&self.vec, &other.vec, |l, r| self.key_of(l).cmp(&self.key_of(r)), |take| {
+            match take {
+                Take::Left(l) => vec.push(l.clone()),
+                Take::Right(r) => vec.push(r.clone()),
+                Take::Both(l, r) => {
+                    match choose_equal(l, r) {
+                        LeftOrRight::Left => vec.push(l.clone()),
+                        LeftOrRight::Right => vec.push(r.clone()),
+                    }
+                }
+            }
+        });
+
+        debug_assert!(is_sorted_unique_by(&vec, &self.key));
+        SortedUniqueVecBy { vec: vec, key: self.key.clone() }
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> AsRef<[T]> for SortedUniqueVecBy<T, K, F> {
+    fn as_ref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Deref for SortedUniqueVecBy<T, K, F> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+#[test]
+fn test_sorted_by_insert_and_range() {
+    let mut s = SortedUniqueVecBy::new(|pair: &(i32, &str)| pair.0);
+    s.insert((5, "five"));
+    s.insert((1, "one"));
+    s.insert((8, "eight"));
+    assert_eq!(&[(1, "one"), (5, "five"), (8, "eight")][..], s.as_ref());
+    assert_eq!(&[(1, "one"), (5, "five")][..], s.range(0..6));
+    assert!(s.contains_key(&5));
+    assert_eq!(Some(&(5, "five")), s.get(&5));
+}
+
+#[test]
+#[should_panic]
+fn test_sorted_by_insert_duplicate_key_panics() {
+    let mut s = SortedUniqueVecBy::new(|pair: &(i32, &str)| pair.0);
+    s.insert((5, "five"));
+    s.insert((5, "FIVE"));
+}
+
+#[test]
+fn test_sorted_by_insert_with_capturing_closure() {
+    let offset = 100;
+    let mut s = SortedUniqueVecBy::new(|pair: &(i32, &str)| pair.0 + offset);
+    s.insert((5, "five"));
+    s.insert((1, "one"));
+    assert_eq!(&[(1, "one"), (5, "five")][..], s.as_ref());
+}
+
+#[cfg(test)]
+fn pair_key(pair: &(i32, i32)) -> i32 {
+    pair.0
+}
+
+#[test]
+fn test_sorted_by_merge() {
+    let mut s1 = SortedUniqueVecBy::new(pair_key);
+    s1.insert((1, 10));
+    s1.insert((5, 50));
+
+    let mut s2 = SortedUniqueVecBy::new(pair_key);
+    s2.insert((1, 100));
+    s2.insert((8, 80));
+
+    let merged = s1.merge(&s2, &|_, _| LeftOrRight::Left);
+    assert_eq!(&[(1, 10), (5, 50), (8, 80)][..], merged.as_ref());
+}